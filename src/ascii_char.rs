@@ -0,0 +1,52 @@
+//! A byte-sized ASCII character type, inspired by the standard library's nightly-only
+//! [`std::ascii::Char`](https://doc.rust-lang.org/std/ascii/enum.Char.html).
+
+/// A single ASCII character.
+///
+/// Wraps a `u8` with the invariant that it always falls in `0..=0x7F`, so it can be used
+/// as a zero-validation, branch-free byte without risking accidental non-ASCII content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct AsciiChar(u8);
+
+impl AsciiChar {
+    /// Creates an `AsciiChar` from `byte`, or `None` if it is not in `0..=0x7F`.
+    pub fn new(byte: u8) -> Option<Self> {
+        if byte <= 0x7f {
+            Some(AsciiChar(byte))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the underlying byte.
+    pub fn as_byte(self) -> u8 {
+        self.0
+    }
+
+    /// Returns the character as a single-byte string slice.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(std::slice::from_ref(&self.0)).unwrap()
+    }
+
+    /// Returns the upper case equivalent of this character.
+    pub fn to_ascii_uppercase(self) -> Self {
+        AsciiChar(self.0.to_ascii_uppercase())
+    }
+
+    /// Returns the lower case equivalent of this character.
+    pub fn to_ascii_lowercase(self) -> Self {
+        AsciiChar(self.0.to_ascii_lowercase())
+    }
+}
+
+/// Converts a slice of [`AsciiChar`]s into an owned `String`.
+pub trait AsciiCharsExt {
+    /// Converts the ASCII characters into an owned `String`.
+    fn to_string(&self) -> String;
+}
+
+impl AsciiCharsExt for [AsciiChar] {
+    fn to_string(&self) -> String {
+        self.iter().map(|c| c.as_byte() as char).collect()
+    }
+}
@@ -9,14 +9,21 @@
 //!     use_upper_case: true,
 //!     use_lower_case: true,
 //! };
-//! let _random_string = gen_random_string(32, output_chars_type);
+//! let _random_string = gen_random_string(32, output_chars_type).unwrap();
 //! ```
-//! 
+//!
+
+mod ascii_char;
 
 use rand::{self, Rng};
+use std::ops::RangeInclusive;
+use unicode_width::UnicodeWidthChar;
+use unicode_xid::UnicodeXID;
+
+pub use ascii_char::{AsciiChar, AsciiCharsExt};
 
 /// Configuration for output characters.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub enum OutputCharsType {
     /// Latin-Alphabet specifying to use upper/lower case.
     LatinAlphabet {
@@ -34,8 +41,65 @@ pub enum OutputCharsType {
     PrintableAsciiWithoutSpace,
     /// Printable ASCII characters *with* SPACE. (0x20-0x7E)
     PrintableAsciiWithSpace,
+    /// Characters that form a valid programming-language identifier.
+    ///
+    /// The first character is drawn from the Unicode `XID_start` property and
+    /// every subsequent character from `XID_continue`.
+    Identifier,
+    /// Arbitrary Unicode code point ranges, e.g. emoji, Greek, Cyrillic or CJK blocks.
+    ///
+    /// A range is picked with a probability weighted by its size, so the output stays
+    /// unbiased across the full union of ranges. Surrogate code points and non-NULL
+    /// control characters are rejected and resampled, since they have no printable form.
+    ///
+    /// Passing an empty `Vec`, a range with start greater than end, or ranges whose
+    /// union contains no addressable code point is an error; see
+    /// [`gen_random_string_with_rng`].
+    UnicodeRanges(Vec<RangeInclusive<u32>>),
+}
+
+/// Errors that can occur while generating a random string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenError {
+    /// `OutputCharsType::UnicodeRanges` was given an empty list of ranges.
+    EmptyUnicodeRanges,
+    /// `OutputCharsType::UnicodeRanges` was given a range whose start is greater than
+    /// its end, e.g. `5..=3`.
+    InvalidUnicodeRange,
+    /// [`gen_random_ascii`] was given an `output_chars_type` that cannot guarantee
+    /// ASCII-only output.
+    NotAsciiCompatible,
+    /// `OutputCharsType::UnicodeRanges` was given ranges whose union contains no
+    /// addressable output: every code point in it is a surrogate (`0xD800..=0xDFFF`),
+    /// a non-NULL control character, or above `U+10FFFF`. For
+    /// [`gen_random_string_to_width`], NUL counts as unaddressable too.
+    NoAddressableUnicodeCodePoints,
 }
 
+impl std::fmt::Display for GenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenError::EmptyUnicodeRanges => {
+                write!(f, "OutputCharsType::UnicodeRanges requires at least one range")
+            }
+            GenError::InvalidUnicodeRange => write!(
+                f,
+                "OutputCharsType::UnicodeRanges contains a range whose start is greater than its end"
+            ),
+            GenError::NotAsciiCompatible => write!(
+                f,
+                "this OutputCharsType cannot guarantee ASCII-only output"
+            ),
+            GenError::NoAddressableUnicodeCodePoints => write!(
+                f,
+                "OutputCharsType::UnicodeRanges ranges contain no addressable code point (only surrogates, non-NULL control characters, code points above U+10FFFF, or, for display-width generation, NUL)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenError {}
+
 /// Generates a random characters string.
 /// 
 /// This function uses [ThreadRng](https://docs.rs/rand/0.6.5/rand/rngs/struct.ThreadRng.html) in [rand crate](https://docs.rs/rand) internally.
@@ -49,9 +113,30 @@ pub enum OutputCharsType {
 ///     use_upper_case: true,
 ///     use_lower_case: true,
 /// };
-/// let _random_string = gen_random_string(32, output_chars_type);
+/// let _random_string = gen_random_string(32, output_chars_type).unwrap();
 /// ```
-pub fn gen_random_string(number_of_characters: usize, output_char_type: OutputCharsType) -> String {
+///
+/// # Errors
+///
+/// Returns [`GenError::EmptyUnicodeRanges`] if `output_char_type` is
+/// `OutputCharsType::UnicodeRanges(vec![])`, [`GenError::InvalidUnicodeRange`] if any
+/// of its ranges has a start greater than its end, or
+/// [`GenError::NoAddressableUnicodeCodePoints`] if the ranges' union contains only
+/// surrogates, non-NULL control characters, or code points above `U+10FFFF`.
+///
+/// ```
+/// use rsgen::{gen_random_string, GenError, OutputCharsType};
+///
+/// let output_chars_type = OutputCharsType::UnicodeRanges(vec![]);
+/// assert_eq!(
+///     gen_random_string(32, output_chars_type),
+///     Err(GenError::EmptyUnicodeRanges)
+/// );
+/// ```
+pub fn gen_random_string(
+    number_of_characters: usize,
+    output_char_type: OutputCharsType,
+) -> Result<String, GenError> {
     let mut rng = rand::thread_rng();
     gen_random_string_with_rng(&mut rng, number_of_characters, output_char_type)
 }
@@ -76,13 +161,248 @@ pub fn gen_random_string(number_of_characters: usize, output_char_type: OutputCh
 ///     .map(|d| d.as_secs())
 ///     .unwrap();
 /// let mut rng = XorShiftRng::seed_from_u64(seed);
-/// let _random_string = gen_random_string_with_rng(&mut rng, 32, output_chars_type);
+/// let _random_string = gen_random_string_with_rng(&mut rng, 32, output_chars_type).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns [`GenError::EmptyUnicodeRanges`] if `output_chars_type` is
+/// `OutputCharsType::UnicodeRanges(vec![])`, [`GenError::InvalidUnicodeRange`] if any
+/// of its ranges has a start greater than its end, or
+/// [`GenError::NoAddressableUnicodeCodePoints`] if the ranges' union contains only
+/// surrogates, non-NULL control characters, or code points above `U+10FFFF`.
+///
+/// ```
+/// use rsgen::{gen_random_string_with_rng, GenError, OutputCharsType};
+///
+/// let output_chars_type = OutputCharsType::UnicodeRanges(vec![5..=3]);
+/// assert_eq!(
+///     gen_random_string_with_rng(&mut rand::thread_rng(), 32, output_chars_type),
+///     Err(GenError::InvalidUnicodeRange)
+/// );
 /// ```
 pub fn gen_random_string_with_rng<R>(
     rng: &mut R,
     number_of_characters: usize,
     output_chars_type: OutputCharsType,
-) -> String
+) -> Result<String, GenError>
+where
+    R: Rng,
+{
+    validate_output_chars_type(&output_chars_type)?;
+    Ok((0..number_of_characters)
+        .map(|i| gen_random_char_with_rng(rng, &output_chars_type, i == 0))
+        .collect())
+}
+
+/// Generates a random characters string that fills up to a target *display width*,
+/// counted in terminal columns, rather than a fixed number of characters.
+///
+/// This is useful for aligning generated tokens in fixed-width columns / tables.
+/// Most characters contribute 1 or 2 columns to the total, following the East Asian
+/// Width rules: code points in the Wide and Fullwidth ranges count as 2, and
+/// "Ambiguous"-width code points count as 2 only when `cjk` is `true` (otherwise 1).
+/// Zero-width characters, such as NUL or combining marks, are appended but add no
+/// columns. Control characters (other than NUL) have no defined width and are skipped.
+///
+/// A character is only appended if doing so would not push the accumulated width
+/// past `target_columns`, so the returned string's width never overshoots it.
+///
+/// # Example
+///
+/// ```
+/// use rsgen::{gen_random_string_to_width, OutputCharsType};
+///
+/// let output_chars_type = OutputCharsType::PrintableAsciiWithoutSpace;
+/// let _random_string = gen_random_string_to_width(&mut rand::thread_rng(), 32, output_chars_type, false).unwrap();
+/// ```
+///
+/// # Errors
+///
+/// Returns [`GenError::EmptyUnicodeRanges`] if `output_chars_type` is
+/// `OutputCharsType::UnicodeRanges(vec![])`, [`GenError::InvalidUnicodeRange`] if any
+/// of its ranges has a start greater than its end, or
+/// [`GenError::NoAddressableUnicodeCodePoints`] if the ranges' union contains only
+/// surrogates, non-NULL control characters, code points above `U+10FFFF`, or NUL
+/// (which, unlike the other modes, is rejected here because its display width is zero).
+///
+/// ```
+/// use rsgen::{gen_random_string_to_width, GenError, OutputCharsType};
+///
+/// // The entire surrogate gap: every code point in it fails `char::from_u32`.
+/// let output_chars_type = OutputCharsType::UnicodeRanges(vec![0xD800..=0xDFFF]);
+/// assert_eq!(
+///     gen_random_string_to_width(&mut rand::thread_rng(), 32, output_chars_type, false),
+///     Err(GenError::NoAddressableUnicodeCodePoints)
+/// );
+/// ```
+///
+/// ```
+/// use rsgen::{gen_random_string_to_width, GenError, OutputCharsType};
+///
+/// // NUL is addressable for `gen_random_string`, but has no display width to fill with.
+/// let output_chars_type = OutputCharsType::UnicodeRanges(vec![0..=0]);
+/// assert_eq!(
+///     gen_random_string_to_width(&mut rand::thread_rng(), 32, output_chars_type, false),
+///     Err(GenError::NoAddressableUnicodeCodePoints)
+/// );
+/// ```
+pub fn gen_random_string_to_width<R>(
+    rng: &mut R,
+    target_columns: usize,
+    output_chars_type: OutputCharsType,
+    cjk: bool,
+) -> Result<String, GenError>
+where
+    R: Rng,
+{
+    validate_output_chars_type(&output_chars_type)?;
+    if let OutputCharsType::UnicodeRanges(ranges) = &output_chars_type {
+        if !has_addressable_code_point(ranges, &[0..=0]) {
+            return Err(GenError::NoAddressableUnicodeCodePoints);
+        }
+    }
+    let mut result = String::new();
+    let mut width_used = 0;
+    loop {
+        let c = gen_random_char_with_rng(rng, &output_chars_type, result.is_empty());
+        let char_width = if cjk { c.width_cjk() } else { c.width() };
+        let char_width = match char_width {
+            Some(width) => width,
+            None => continue,
+        };
+        if width_used + char_width > target_columns {
+            break;
+        }
+        result.push(c);
+        width_used += char_width;
+        if width_used == target_columns {
+            break;
+        }
+    }
+    Ok(result)
+}
+
+/// Generates a random ASCII characters buffer specifying RNG.
+///
+/// Unlike [`gen_random_string_with_rng`], this skips the intermediate `char` to UTF-8
+/// `String` conversion and returns [`AsciiChar`]s whose ASCII-ness is statically
+/// guaranteed, which suits callers that feed the output straight into byte protocols
+/// such as tokens or keys. Use [`AsciiCharsExt::to_string`] to turn the result back
+/// into a `String`.
+///
+/// # Example
+///
+/// ```
+/// use rsgen::{gen_random_ascii, AsciiCharsExt, OutputCharsType};
+///
+/// let output_chars_type = OutputCharsType::PrintableAsciiWithoutSpace;
+/// let ascii_chars = gen_random_ascii(&mut rand::thread_rng(), 32, output_chars_type).unwrap();
+/// let _random_string = ascii_chars.to_string();
+/// ```
+///
+/// # Errors
+///
+/// Returns [`GenError::NotAsciiCompatible`] if `output_chars_type` is
+/// `OutputCharsType::Identifier` or `OutputCharsType::UnicodeRanges`, neither of which
+/// can guarantee ASCII-only output.
+///
+/// ```
+/// use rsgen::{gen_random_ascii, GenError, OutputCharsType};
+///
+/// let output_chars_type = OutputCharsType::Identifier;
+/// assert_eq!(
+///     gen_random_ascii(&mut rand::thread_rng(), 32, output_chars_type),
+///     Err(GenError::NotAsciiCompatible)
+/// );
+/// ```
+pub fn gen_random_ascii<R>(
+    rng: &mut R,
+    number_of_characters: usize,
+    output_chars_type: OutputCharsType,
+) -> Result<Vec<AsciiChar>, GenError>
+where
+    R: Rng,
+{
+    match output_chars_type {
+        OutputCharsType::Identifier | OutputCharsType::UnicodeRanges(_) => {
+            return Err(GenError::NotAsciiCompatible)
+        }
+        _ => {}
+    }
+    Ok((0..number_of_characters)
+        .map(|i| {
+            let c = gen_random_char_with_rng(rng, &output_chars_type, i == 0);
+            AsciiChar::new(c as u8).expect("output_chars_type guarantees ASCII output")
+        })
+        .collect())
+}
+
+fn validate_output_chars_type(output_chars_type: &OutputCharsType) -> Result<(), GenError> {
+    if let OutputCharsType::UnicodeRanges(ranges) = output_chars_type {
+        if ranges.is_empty() {
+            return Err(GenError::EmptyUnicodeRanges);
+        }
+        if ranges.iter().any(|range| range.is_empty()) {
+            return Err(GenError::InvalidUnicodeRange);
+        }
+        if !has_addressable_code_point(ranges, &[]) {
+            return Err(GenError::NoAddressableUnicodeCodePoints);
+        }
+    }
+    Ok(())
+}
+
+/// Returns `true` if the union of `ranges` contains at least one code point that is
+/// neither a surrogate, a non-NULL control character, nor above `U+10FFFF`, i.e. one
+/// that [`gen_random_char_with_rng`]'s reject-resample loop could actually produce.
+///
+/// `extra_forbidden` lets callers exclude further code points that are addressable in
+/// general but unusable for their specific purpose, e.g. NUL for display-width fills.
+fn has_addressable_code_point(
+    ranges: &[RangeInclusive<u32>],
+    extra_forbidden: &[RangeInclusive<u32>],
+) -> bool {
+    const FORBIDDEN: [RangeInclusive<u32>; 4] =
+        [0x01..=0x1F, 0x7F..=0x9F, 0xD800..=0xDFFF, 0x110000..=u32::MAX];
+    let mut remaining: Vec<RangeInclusive<u32>> = ranges.to_vec();
+    for forbidden in FORBIDDEN.iter().chain(extra_forbidden) {
+        remaining = subtract_range(remaining, forbidden);
+    }
+    !remaining.is_empty()
+}
+
+/// Removes `forbidden` from each range in `ranges`, splitting a range in two if
+/// `forbidden` falls strictly inside it.
+fn subtract_range(
+    ranges: Vec<RangeInclusive<u32>>,
+    forbidden: &RangeInclusive<u32>,
+) -> Vec<RangeInclusive<u32>> {
+    ranges
+        .into_iter()
+        .flat_map(|range| {
+            let (start, end) = (*range.start(), *range.end());
+            let (fstart, fend) = (*forbidden.start(), *forbidden.end());
+            if fend < start || fstart > end {
+                return vec![range];
+            }
+            let mut pieces = Vec::new();
+            if start < fstart {
+                pieces.push(start..=fstart - 1);
+            }
+            if end > fend {
+                pieces.push(fend + 1..=end);
+            }
+            pieces
+        })
+        .collect()
+}
+
+fn gen_random_char_with_rng<R>(
+    rng: &mut R,
+    output_chars_type: &OutputCharsType,
+    is_first_char: bool,
+) -> char
 where
     R: Rng,
 {
@@ -102,10 +422,7 @@ where
                 _ => unreachable!(),
             };
             let uniformed = rand::distributions::Uniform::from(0..range);
-            rng.sample_iter(&uniformed)
-                .take(number_of_characters)
-                .map(|n| charset[n as usize] as char)
-                .collect()
+            charset[rng.sample(uniformed) as usize] as char
         }
         OutputCharsType::LatinAlphabetAndNumeric {
             use_upper_case,
@@ -122,31 +439,63 @@ where
                 _ => unreachable!(),
             };
             let uniformed = rand::distributions::Uniform::from(0..range);
-            rng.sample_iter(&uniformed)
-                .take(number_of_characters)
-                .map(|n| charset[n] as char)
-                .collect()
+            charset[rng.sample(uniformed)] as char
         }
         OutputCharsType::Numeric => {
             let uniform = rand::distributions::Uniform::from(0..=9);
-            rng.sample_iter(&uniform)
-                .take(number_of_characters)
-                .filter_map(|n| std::char::from_digit(n as u32, 10))
-                .collect()
+            std::char::from_digit(rng.sample(uniform), 10).unwrap()
         }
         OutputCharsType::PrintableAsciiWithoutSpace => {
             let uniform = rand::distributions::Uniform::from(0x21..=0x7e);
-            rng.sample_iter(&uniform)
-                .take(number_of_characters)
-                .filter_map(std::char::from_u32)
-                .collect()
+            std::char::from_u32(rng.sample(uniform)).unwrap()
         }
         OutputCharsType::PrintableAsciiWithSpace => {
             let uniform = rand::distributions::Uniform::from(0x20..=0x7e);
-            rng.sample_iter(&uniform)
-                .take(number_of_characters)
-                .filter_map(std::char::from_u32)
-                .collect()
+            std::char::from_u32(rng.sample(uniform)).unwrap()
+        }
+        OutputCharsType::Identifier => {
+            let uniform = rand::distributions::Uniform::from(0..=0x10ffffu32);
+            loop {
+                let code_point = rng.sample(uniform);
+                if let Some(c) = std::char::from_u32(code_point) {
+                    let is_valid = if is_first_char {
+                        c.is_xid_start()
+                    } else {
+                        c.is_xid_continue()
+                    };
+                    if is_valid {
+                        break c;
+                    }
+                }
+            }
+        }
+        OutputCharsType::UnicodeRanges(ranges) => loop {
+            let code_point = sample_unicode_range_code_point(rng, ranges);
+            if let Some(c) = std::char::from_u32(code_point) {
+                if c == '\0' || !c.is_control() {
+                    break c;
+                }
+            }
+        },
+    }
+}
+
+fn sample_unicode_range_code_point<R>(rng: &mut R, ranges: &[RangeInclusive<u32>]) -> u32
+where
+    R: Rng,
+{
+    let sizes: Vec<u64> = ranges
+        .iter()
+        .map(|range| u64::from(*range.end() - *range.start()) + 1)
+        .collect();
+    let total: u64 = sizes.iter().sum();
+    let uniform = rand::distributions::Uniform::from(0..total);
+    let mut offset = rng.sample(uniform);
+    for (range, size) in ranges.iter().zip(sizes.iter()) {
+        if offset < *size {
+            return range.start() + offset as u32;
         }
+        offset -= *size;
     }
+    unreachable!()
 }
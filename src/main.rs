@@ -63,6 +63,8 @@
 //! - `--only-upper-case` option sets to use upper case letters only.
 //! - `--only-lower-case` option sets to use lower case letters only.
 //! - `--only-latin-alphabet` option sets to use the Latin alphabet only, *not* includes numeric characters.
+//! - `--identifier` option sets to generate a string that is guaranteed to be a valid programming-language identifier.
+//! - `--unicode-range START-END` option (repeatable) restricts the output to the given hexadecimal Unicode code point range(s).
 //!
 //! ## License
 //!
@@ -73,6 +75,7 @@ use clap;
 use rand_core::SeedableRng;
 use rand_xorshift;
 use rsgen::{gen_random_string_with_rng, OutputCharsType};
+use std::ops::RangeInclusive;
 use std::time::SystemTime;
 
 fn argument_validator(v: String) -> Result<(), String> {
@@ -85,6 +88,28 @@ fn argument_validator(v: String) -> Result<(), String> {
     }
 }
 
+fn parse_unicode_range(v: &str) -> Result<RangeInclusive<u32>, String> {
+    let error_message = || {
+        format!(
+            "Invalid unicode range '{}'. Expected format: START-END (hexadecimal code points).",
+            v
+        )
+    };
+    let mut parts = v.splitn(2, '-');
+    let start = parts.next().ok_or_else(error_message)?;
+    let end = parts.next().ok_or_else(error_message)?;
+    let start = u32::from_str_radix(start, 16).map_err(|_| error_message())?;
+    let end = u32::from_str_radix(end, 16).map_err(|_| error_message())?;
+    if start > end {
+        return Err(error_message());
+    }
+    Ok(start..=end)
+}
+
+fn unicode_range_validator(v: String) -> Result<(), String> {
+    parse_unicode_range(&v).map(|_| ())
+}
+
 fn main() {
     let matches = clap::App::new(clap::crate_name!())
         .version(clap::crate_version!())
@@ -115,21 +140,54 @@ fn main() {
                 .help("Restricts the output to be numeric.")
                 .short("n")
                 .long("numeric")
-                .conflicts_with_all(&["printable-ascii", "printable-ascii-s"]),
+                .conflicts_with_all(&["printable-ascii", "printable-ascii-s", "identifier", "unicode-range"]),
         )
         .arg(
             clap::Arg::with_name("printable-ascii")
                 .help("Uses the printable ASCII characters without SPACE. (0x21-0x7E)")
                 .short("p")
                 .long("printable-ascii")
-                .conflicts_with_all(&["numeric", "printable-ascii-s"]),
+                .conflicts_with_all(&["numeric", "printable-ascii-s", "identifier", "unicode-range"]),
         )
         .arg(
             clap::Arg::with_name("printable-ascii-s")
                 .help("Uses the printable ASCII characters WITH SPACE. (0x20-0x7E)")
                 .short("P")
                 .long("printable-ascii-with-space")
-                .conflicts_with_all(&["numeric", "printable-ascii"]),
+                .conflicts_with_all(&["numeric", "printable-ascii", "identifier", "unicode-range"]),
+        )
+        .arg(
+            clap::Arg::with_name("identifier")
+                .help("Generates a string that is guaranteed to be a valid programming-language identifier.")
+                .long("identifier")
+                .conflicts_with_all(&[
+                    "numeric",
+                    "printable-ascii",
+                    "printable-ascii-s",
+                    "only-upper-case",
+                    "only-lower-case",
+                    "only-latin-alphabet",
+                    "unicode-range",
+                ]),
+        )
+        .arg(
+            clap::Arg::with_name("unicode-range")
+                .help("Generates characters from the given Unicode code point range (hexadecimal, e.g. 1F600-1F64F). May be given multiple times.")
+                .long("unicode-range")
+                .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
+                .validator(unicode_range_validator)
+                .value_name("START-END")
+                .conflicts_with_all(&[
+                    "numeric",
+                    "printable-ascii",
+                    "printable-ascii-s",
+                    "identifier",
+                    "only-upper-case",
+                    "only-lower-case",
+                    "only-latin-alphabet",
+                ]),
         )
         .arg(
             clap::Arg::with_name("fast")
@@ -153,7 +211,7 @@ fn main() {
             clap::Arg::with_name("only-latin-alphabet")
                 .help("Uses the Latin alphabet only, NOT includes numeric characters.")
                 .long("only-latin-alphabet")
-                .conflicts_with_all(&["numeric", "printable-ascii", "printable-ascii-s"]),
+                .conflicts_with_all(&["numeric", "printable-ascii", "printable-ascii-s", "identifier", "unicode-range"]),
         )
         .get_matches();
 
@@ -172,6 +230,13 @@ fn main() {
         OutputCharsType::PrintableAsciiWithoutSpace
     } else if matches.is_present("printable-ascii-s") {
         OutputCharsType::PrintableAsciiWithSpace
+    } else if matches.is_present("identifier") {
+        OutputCharsType::Identifier
+    } else if let Some(ranges) = matches.values_of("unicode-range") {
+        let ranges = ranges
+            .map(|v| parse_unicode_range(v).expect("validated by clap"))
+            .collect();
+        OutputCharsType::UnicodeRanges(ranges)
     } else {
         let is_upper_only = matches.is_present("only-upper-case");
         let is_lower_only = matches.is_present("only-lower-case");
@@ -224,14 +289,25 @@ fn main() {
             .unwrap();
         let mut rng = rand_xorshift::XorShiftRng::seed_from_u64(seed);
         iterator
-            .map(|_| gen_random_string_with_rng(&mut rng, number_of_characters, output_chars_type))
+            .map(|_| {
+                gen_random_string_with_rng(&mut rng, number_of_characters, output_chars_type.clone())
+                    .unwrap_or_else(|e| exit_with_error(&e))
+            })
             .enumerate()
             .for_each(printing);
     } else {
         let mut rng = rand::thread_rng();
         iterator
-            .map(|_| gen_random_string_with_rng(&mut rng, number_of_characters, output_chars_type))
+            .map(|_| {
+                gen_random_string_with_rng(&mut rng, number_of_characters, output_chars_type.clone())
+                    .unwrap_or_else(|e| exit_with_error(&e))
+            })
             .enumerate()
             .for_each(printing);
     }
 }
+
+fn exit_with_error(e: &rsgen::GenError) -> ! {
+    eprintln!("Error: {}", e);
+    std::process::exit(1);
+}